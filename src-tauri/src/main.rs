@@ -3,10 +3,15 @@
     windows_subsystem = "windows"
 )]
 
+mod reload;
+mod slider;
 mod website;
+mod window_state;
 
+use std::collections::HashMap;
 use std::error::Error;
-use std::sync::Mutex;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 use tauri::api::dialog;
 use tauri::utils::config::WindowUrl;
@@ -17,6 +22,8 @@ use tauri::{
     SystemTraySubmenu,
 };
 
+use window_state::{PersistedState, StateFlags};
+
 
 struct WebsiteState {
     current_id: Mutex<usize>,
@@ -26,7 +33,35 @@ struct WebsiteState {
 
 struct AppState {
     website: Mutex<Option<WebsiteState>>,
-    visible: Mutex<bool>
+    visible: Mutex<bool>,
+    persisted: Mutex<PersistedState>,
+    persisted_path: Mutex<Option<PathBuf>>,
+    all_workspaces: Mutex<HashMap<String, bool>>,
+    slider: Arc<slider::Slider>,
+}
+
+fn window_state_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+    app.path_resolver()
+        .app_config_dir()
+        .map(|dir| dir.join("window-state.json"))
+}
+
+fn flush_window_states(app: &tauri::AppHandle) {
+    let state = app.state::<AppState>();
+    let path = state.persisted_path.lock().unwrap();
+    if let Some(path) = path.as_ref() {
+        let mut persisted = state.persisted.lock().unwrap();
+        for (label, window) in app.windows().into_iter() {
+            persisted.update_window(&label, &window);
+        }
+        if let Some(website) = state.website.lock().unwrap().as_ref() {
+            persisted.current_id = *website.current_id.lock().unwrap();
+        }
+        persisted.visible = *state.visible.lock().unwrap();
+        if let Err(err) = persisted.save(path) {
+            eprintln!("failed to save window state: {}", err);
+        }
+    }
 }
 
 
@@ -63,9 +98,187 @@ fn trigger_visible(app: &tauri::AppHandle) {
         app.get_window(&format!("window-{}", *current_id)).unwrap().show().unwrap();
         app.tray_handle().get_item("visible").set_title("Hide").unwrap();
     }
+    drop(visible);
+    state.slider.restart_timer();
 }
 
 
+fn build_website_window<M: tauri::Manager<tauri::Wry>>(
+    manager: &M,
+    website_info: &website::WebSiteInfo,
+    i: usize,
+    website: &website::WebSite,
+) -> Result<Window, Box<dyn Error>> {
+    let allowed_hosts = website.allowed_hosts()?;
+    let window = WindowBuilder::new(
+        manager,
+        format!("window-{}", i),
+        WindowUrl::External(website.url.parse()?),
+    )
+    .skip_taskbar(true)
+    .decorations(false)
+    .title(&website.name)
+    .on_navigation(move |url| match url.host_str() {
+        Some(host) => website::host_allowed(host, &allowed_hosts),
+        None => false,
+    })
+    .visible_on_all_workspaces(website_info.visible_on_all_workspaces(website))
+    .build()?;
+    Ok(window)
+}
+
+fn build_tray_menu(
+    website_info: &website::WebSiteInfo,
+    visible: bool,
+    on_all_workspaces: bool,
+    rotation_paused: bool,
+) -> SystemTrayMenu {
+    let mut sub_menu = SystemTrayMenu::new();
+    for (i, website) in website_info.websites.iter().enumerate() {
+        sub_menu = sub_menu.add_item(CustomMenuItem::new(
+            format!("window-{}", i),
+            website.name.clone(),
+        ));
+    }
+    let mut menu = SystemTrayMenu::new()
+        .add_submenu(SystemTraySubmenu::new("Websites", sub_menu))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("reset".to_string(), "Reset"))
+        .add_item(CustomMenuItem::new("restart".to_string(), "Restart"))
+        .add_item(CustomMenuItem::new(
+            "visible".to_string(),
+            if visible { "Hide" } else { "Show" },
+        ))
+        .add_item(CustomMenuItem::new(
+            "toggle_all_workspaces".to_string(),
+            if on_all_workspaces {
+                "Don't Show on All Workspaces"
+            } else {
+                "Show on All Workspaces"
+            },
+        ));
+    if website_info.slider.is_some() {
+        menu = menu
+            .add_item(CustomMenuItem::new(
+                "pause_rotation".to_string(),
+                if rotation_paused {
+                    "Resume rotation"
+                } else {
+                    "Pause rotation"
+                },
+            ))
+            .add_item(CustomMenuItem::new("previous".to_string(), "Previous"))
+            .add_item(CustomMenuItem::new("next".to_string(), "Next"));
+    }
+    menu.add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("quit".to_string(), "Quit"))
+}
+
+fn close_window_and_wait(window: Window, label: &str, app: &tauri::AppHandle) {
+    let _ = window.close();
+    for _ in 0..50 {
+        if app.get_window(label).is_none() {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+}
+
+fn apply_website_info(app: &tauri::AppHandle, new_info: website::WebSiteInfo) {
+    let state = app.state::<AppState>();
+    let mut website_guard = state.website.lock().unwrap();
+    let old_state = match website_guard.as_ref() {
+        Some(w) => w,
+        None => return,
+    };
+    let old_info = old_state.website_info.clone();
+    let old_current_name = old_info
+        .websites
+        .get(*old_state.current_id.lock().unwrap())
+        .map(|w| w.name.clone());
+
+    let mut all_workspaces = state.all_workspaces.lock().unwrap();
+    let max_len = old_info.websites.len().max(new_info.websites.len());
+    for i in 0..max_len {
+        let label = format!("window-{}", i);
+        let unchanged = match (old_info.websites.get(i), new_info.websites.get(i)) {
+            (Some(a), Some(b)) => {
+                a.name == b.name
+                    && a.url == b.url
+                    && a.allow_navigation == b.allow_navigation
+                    && old_info.visible_on_all_workspaces(a) == new_info.visible_on_all_workspaces(b)
+            }
+            _ => false,
+        };
+        if unchanged {
+            continue;
+        }
+        if let Some(window) = app.get_window(&label) {
+            close_window_and_wait(window, &label, app);
+            all_workspaces.remove(&label);
+        }
+        if let Some(website) = new_info.websites.get(i) {
+            match build_website_window(app, &new_info, i, website) {
+                Ok(window) => {
+                    reset(&window);
+                    window.hide().unwrap();
+                    all_workspaces.insert(label, new_info.visible_on_all_workspaces(website));
+                }
+                Err(err) => eprintln!("failed to create window for {}: {}", website.name, err),
+            }
+        }
+    }
+    drop(all_workspaces);
+
+    let mut current_id = old_current_name
+        .as_ref()
+        .and_then(|name| new_info.websites.iter().position(|w| &w.name == name))
+        .unwrap_or_else(|| {
+            new_info
+                .websites
+                .iter()
+                .position(|w| w.name == new_info.default)
+                .unwrap_or(0)
+        });
+    if current_id >= new_info.websites.len() {
+        current_id = 0;
+    }
+
+    let slider_changed = old_info.slider != new_info.slider;
+    *website_guard = Some(WebsiteState {
+        current_id: Mutex::new(current_id),
+        website_info: new_info.clone(),
+    });
+    drop(website_guard);
+
+    if slider_changed {
+        state
+            .slider
+            .set_duration(new_info.slider.map(std::time::Duration::from_secs));
+    }
+
+    let visible = *state.visible.lock().unwrap() && !new_info.websites.is_empty();
+    if visible {
+        if let Some(window) = app.get_window(&format!("window-{}", current_id)) {
+            window.show().unwrap();
+        }
+    }
+    let on_all_workspaces = *state
+        .all_workspaces
+        .lock()
+        .unwrap()
+        .get(&format!("window-{}", current_id))
+        .unwrap_or(&false);
+    app.tray_handle()
+        .set_menu(build_tray_menu(
+            &new_info,
+            visible,
+            on_all_workspaces,
+            state.slider.is_paused(),
+        ))
+        .unwrap();
+}
+
 fn system_tray_event_handler(app: &tauri::AppHandle, event: tauri::SystemTrayEvent) -> () {
     match event {
         SystemTrayEvent::DoubleClick { .. } => {
@@ -86,6 +299,44 @@ fn system_tray_event_handler(app: &tauri::AppHandle, event: tauri::SystemTrayEve
             "restart" => {
                 app.restart();
             }
+            "toggle_all_workspaces" => {
+                let state = app.state::<AppState>();
+                let website = state.website.lock().unwrap();
+                let current_id = website.as_ref().unwrap().current_id.lock().unwrap();
+                let label = format!("window-{}", *current_id);
+                let mut all_workspaces = state.all_workspaces.lock().unwrap();
+                let on_all_workspaces = !*all_workspaces.get(&label).unwrap_or(&false);
+                all_workspaces.insert(label.clone(), on_all_workspaces);
+                app.get_window(&label)
+                    .unwrap()
+                    .set_visible_on_all_workspaces(on_all_workspaces)
+                    .unwrap();
+                app.tray_handle()
+                    .get_item("toggle_all_workspaces")
+                    .set_title(if on_all_workspaces {
+                        "Don't Show on All Workspaces"
+                    } else {
+                        "Show on All Workspaces"
+                    })
+                    .unwrap();
+            }
+            "pause_rotation" => {
+                let state = app.state::<AppState>();
+                let paused = !state.slider.is_paused();
+                state.slider.set_paused(paused);
+                app.tray_handle()
+                    .get_item("pause_rotation")
+                    .set_title(if paused { "Resume rotation" } else { "Pause rotation" })
+                    .unwrap();
+            }
+            "next" => {
+                let state = app.state::<AppState>();
+                slider::step(app, &state.slider, 1);
+            }
+            "previous" => {
+                let state = app.state::<AppState>();
+                slider::step(app, &state.slider, -1);
+            }
             label => {
                 let (_, id_str) = label.split_once("-").unwrap();
                 let chosen_id: usize = id_str.parse().unwrap();
@@ -100,6 +351,23 @@ fn system_tray_event_handler(app: &tauri::AppHandle, event: tauri::SystemTrayEve
                     .set_title("Hide")
                     .unwrap();
                 *state.visible.lock().unwrap() = true;
+                let on_all_workspaces = *state
+                    .all_workspaces
+                    .lock()
+                    .unwrap()
+                    .get(label)
+                    .unwrap_or(&false);
+                app.tray_handle()
+                    .get_item("toggle_all_workspaces")
+                    .set_title(if on_all_workspaces {
+                        "Don't Show on All Workspaces"
+                    } else {
+                        "Show on All Workspaces"
+                    })
+                    .unwrap();
+                drop(current_id);
+                drop(website);
+                state.slider.restart_timer();
             }
         },
         _ => {}
@@ -108,13 +376,26 @@ fn system_tray_event_handler(app: &tauri::AppHandle, event: tauri::SystemTrayEve
 
 fn run_handler(app: &tauri::AppHandle, event: tauri::RunEvent) {
     match event {
-        tauri::RunEvent::WindowEvent { event, .. } => match event {
+        tauri::RunEvent::WindowEvent { label, event, .. } => match event {
             tauri::WindowEvent::CloseRequested { api, .. } => {
                 api.prevent_close();
                 trigger_visible(app);
             }
+            tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+                if let Some(window) = app.get_window(&label) {
+                    let state = app.state::<AppState>();
+                    state
+                        .persisted
+                        .lock()
+                        .unwrap()
+                        .update_window(&label, &window);
+                }
+            }
             _ => {}
         },
+        tauri::RunEvent::Exit => {
+            flush_window_states(app);
+        }
         _ => {}
     }
 }
@@ -140,68 +421,67 @@ fn setup_handler(app: &mut tauri::App) -> Result<(), Box<dyn Error>> {
     if let Some(config_dir) = app.path_resolver().app_config_dir() {
         let website_info = get_website_info(config_dir.join("websites.json")).unwrap();
         let state = app.state::<AppState>();
+        let state_path = window_state_path(&app.handle());
+        let loaded = state_path.as_ref().and_then(|path| PersistedState::load(path));
+        let persisted = loaded.clone().unwrap_or_default();
         let mut current_id = 0;
-        let mut sub_menu = SystemTrayMenu::new();
+        let mut all_workspaces = HashMap::new();
         for (i, website) in website_info.websites.clone().into_iter().enumerate() {
             let label = format!("window-{}", i);
-            let window = WindowBuilder::new(
-                app,
-                label.clone(),
-                WindowUrl::External(website.url.parse().unwrap()),
-            )
-            .skip_taskbar(true)
-            .decorations(false)
-            .title(&website.name)
-            .build()?;
-            if website.name != website_info.default {
-                window.hide().unwrap();
-            } else {
+            let window = build_website_window(app, &website_info, i, &website)?;
+            let on_all_workspaces = website_info.visible_on_all_workspaces(&website);
+            all_workspaces.insert(label.clone(), on_all_workspaces);
+            match persisted.windows.get(&label) {
+                Some(saved) if window_state::is_position_on_screen(&window, saved) => {
+                    window_state::restore_window(&window, saved, StateFlags::default());
+                    window.hide().unwrap();
+                }
+                _ => {
+                    reset(&window);
+                    window.hide().unwrap();
+                }
+            }
+            if website.name == website_info.default {
                 current_id = i;
             }
-            reset(&window);
-            sub_menu = sub_menu.add_item(CustomMenuItem::new(label, website.name.clone()));
         }
+        *state.all_workspaces.lock().unwrap() = all_workspaces;
+        if let Some(loaded) = &loaded {
+            if loaded.current_id < website_info.websites.len() {
+                current_id = loaded.current_id;
+            }
+        }
+        let visible = loaded.as_ref().map(|p| p.visible).unwrap_or(true);
         *state.website.lock().unwrap() = Some(WebsiteState { current_id: Mutex::new(current_id), website_info: website_info.clone() });
+        *state.visible.lock().unwrap() = visible;
+        *state.persisted.lock().unwrap() = persisted;
+        *state.persisted_path.lock().unwrap() = state_path;
 
-        let tray_menu = SystemTrayMenu::new()
-            .add_submenu(SystemTraySubmenu::new("Websites", sub_menu))
-            .add_native_item(SystemTrayMenuItem::Separator)
-            .add_item(CustomMenuItem::new("reset".to_string(), "Reset"))
-            .add_item(CustomMenuItem::new("restart".to_string(), "Restart"))
-            .add_item(CustomMenuItem::new("visible".to_string(), "Hide"))
-            .add_native_item(SystemTrayMenuItem::Separator)
-            .add_item(CustomMenuItem::new("quit".to_string(), "Quit"));
+        let on_all_workspaces = *state
+            .all_workspaces
+            .lock()
+            .unwrap()
+            .get(&format!("window-{}", current_id))
+            .unwrap_or(&false);
+        let tray_menu = build_tray_menu(
+            &website_info,
+            visible,
+            on_all_workspaces,
+            state.slider.is_paused(),
+        );
         let system_tray = SystemTray::new().with_menu(tray_menu);
         system_tray.build(app)?;
-        if None == website_info.slider {
-            return Ok(());
-        }
-        if website_info.websites.len() < 1 {
-            return Ok(());
+        if !website_info.websites.is_empty() && visible {
+            app.get_window(&format!("window-{}", current_id))
+                .unwrap()
+                .show()
+                .unwrap();
         }
-        let handle = app.handle();
-        let duration = website_info.slider.unwrap();
-        tauri::async_runtime::spawn(async move {
-            loop {
-                std::thread::sleep(std::time::Duration::from_secs(duration));
-                let websites_count = handle.windows().len();
-                if websites_count < 1 {
-                    continue;
-                }
-                let state = handle.state::<AppState>();
-                if !*state.visible.lock().unwrap() {
-                    continue;
-                }
-                let websites = state.website.lock().unwrap();
-                let mut current_id = websites.as_ref().unwrap().current_id.lock().unwrap();
-                let current_total = websites.as_ref().unwrap().website_info.websites.len();
-                let id = *current_id;
-                *current_id += 1;
-                *current_id %= current_total;
-                handle.get_window(&format!("window-{}", id)).unwrap().hide().unwrap();
-                handle.get_window(&format!("window-{}", (id + 1) % current_total)).unwrap().show().unwrap();
-            }
-        });
+        reload::watch(app.handle(), config_dir.join("websites.json"), apply_website_info);
+        state
+            .slider
+            .set_duration(website_info.slider.map(std::time::Duration::from_secs));
+        slider::spawn(app.handle(), state.slider.clone());
         Ok(())
     } else {
         dialog::blocking::MessageDialogBuilder::new("Error!", "no config_dir").show();
@@ -211,7 +491,14 @@ fn setup_handler(app: &mut tauri::App) -> Result<(), Box<dyn Error>> {
 
 fn main() {
     tauri::Builder::default()
-        .manage(AppState { website: Default::default(), visible: Mutex::new(true) })
+        .manage(AppState {
+            website: Default::default(),
+            visible: Mutex::new(true),
+            persisted: Default::default(),
+            persisted_path: Default::default(),
+            all_workspaces: Default::default(),
+            slider: Arc::new(slider::Slider::new()),
+        })
         .on_system_tray_event(system_tray_event_handler)
         .setup(setup_handler)
         .build(tauri::generate_context!())
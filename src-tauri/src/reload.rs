@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use tauri::api::dialog;
+use tauri::AppHandle;
+
+use crate::website::WebSiteInfo;
+
+pub fn watch(app: AppHandle, websites_path: PathBuf, apply: impl Fn(&AppHandle, WebSiteInfo) + Send + 'static) {
+    std::thread::spawn(move || {
+        let parent = match websites_path.parent() {
+            Some(parent) => parent.to_path_buf(),
+            None => return,
+        };
+        let (tx, rx) = channel();
+        let mut watcher = match notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        }) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                eprintln!("failed to start websites.json watcher: {}", err);
+                return;
+            }
+        };
+        if watcher.watch(&parent, RecursiveMode::NonRecursive).is_err() {
+            eprintln!("failed to watch {}", parent.display());
+            return;
+        }
+        for res in rx {
+            let event = match res {
+                Ok(event) => event,
+                Err(err) => {
+                    eprintln!("websites.json watch error: {}", err);
+                    continue;
+                }
+            };
+            if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                continue;
+            }
+            if !event.paths.iter().any(|path| path == &websites_path) {
+                continue;
+            }
+            // give editors that write via a temp file + rename a moment to settle
+            std::thread::sleep(Duration::from_millis(200));
+            match WebSiteInfo::from_json(websites_path.clone()) {
+                Ok(new_info) => apply(&app, new_info),
+                Err(err) => {
+                    dialog::blocking::MessageDialogBuilder::new("Error!", format!("{}", err))
+                        .show();
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Notify;
+
+use crate::AppState;
+
+pub struct Slider {
+    paused: AtomicBool,
+    restart: Notify,
+    duration: std::sync::Mutex<Option<Duration>>,
+}
+
+impl Slider {
+    pub fn new() -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            restart: Notify::new(),
+            duration: std::sync::Mutex::new(None),
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn set_duration(&self, duration: Option<Duration>) {
+        *self.duration.lock().unwrap() = duration;
+        self.restart_timer();
+    }
+
+    pub fn restart_timer(&self) {
+        self.restart.notify_one();
+    }
+}
+
+pub fn spawn(app: AppHandle, slider: Arc<Slider>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let duration = match *slider.duration.lock().unwrap() {
+                Some(duration) => duration,
+                None => {
+                    slider.restart.notified().await;
+                    continue;
+                }
+            };
+            let mut interval = tokio::time::interval(duration);
+            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = slider.restart.notified() => {
+                    continue;
+                }
+            }
+            if slider.is_paused() {
+                continue;
+            }
+            advance(&app, 1);
+        }
+    });
+}
+
+fn advance(app: &AppHandle, delta: i64) {
+    let state = app.state::<AppState>();
+    let website = state.website.lock().unwrap();
+    let website_state = match website.as_ref() {
+        Some(w) => w,
+        None => return,
+    };
+    let total = website_state.website_info.websites.len();
+    if total <= 1 || !*state.visible.lock().unwrap() {
+        return;
+    }
+    let mut current_id = website_state.current_id.lock().unwrap();
+    let from = *current_id;
+    let to = (*current_id as i64 + delta).rem_euclid(total as i64) as usize;
+    *current_id = to;
+    drop(current_id);
+    drop(website);
+    if let Some(window) = app.get_window(&format!("window-{}", from)) {
+        window.hide().unwrap();
+    }
+    if let Some(window) = app.get_window(&format!("window-{}", to)) {
+        window.show().unwrap();
+    }
+}
+
+pub fn step(app: &AppHandle, slider: &Slider, delta: i64) {
+    let state = app.state::<AppState>();
+    *state.visible.lock().unwrap() = true;
+    advance(app, delta);
+    app.tray_handle()
+        .get_item("visible")
+        .set_title("Hide")
+        .unwrap();
+    slider.restart_timer();
+}
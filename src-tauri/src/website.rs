@@ -9,6 +9,8 @@ use std::path::PathBuf;
 pub struct WebSite {
     pub name: String,
     pub url: String,
+    pub allow_navigation: Option<Vec<String>>,
+    pub visible_on_all_workspaces: Option<bool>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -16,14 +18,72 @@ pub struct WebSiteInfo {
     pub websites: Vec<WebSite>,
     pub default: String,
     pub slider: Option<u64>,
+    pub visible_on_all_workspaces: Option<bool>,
+}
+
+impl WebSite {
+    pub fn allowed_hosts(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        match &self.allow_navigation {
+            Some(patterns) => Ok(patterns.clone()),
+            None => {
+                let url = url::Url::parse(&self.url)?;
+                let host = url
+                    .host_str()
+                    .ok_or_else(|| format!("{}: url has no host", self.name))?;
+                Ok(vec![host.to_string()])
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), Box<dyn Error>> {
+        url::Url::parse(&self.url)?;
+        if let Some(patterns) = &self.allow_navigation {
+            if patterns.is_empty() {
+                return Err(format!(
+                    "{}: allow_navigation must not be empty",
+                    self.name
+                )
+                .into());
+            }
+            for pattern in patterns {
+                if pattern.is_empty() || pattern.contains('/') || pattern.contains("://") {
+                    return Err(format!(
+                        "{}: invalid allow_navigation pattern: {}",
+                        self.name, pattern
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn host_allowed(host: &str, patterns: &[String]) -> bool {
+    let host = host.to_ascii_lowercase();
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(suffix) => host == suffix || host.ends_with(&format!(".{}", suffix)),
+            None => host == pattern,
+        }
+    })
 }
 
 impl WebSiteInfo {
+    pub fn visible_on_all_workspaces(&self, website: &WebSite) -> bool {
+        website
+            .visible_on_all_workspaces
+            .or(self.visible_on_all_workspaces)
+            .unwrap_or(false)
+    }
+
     pub fn from_json(path: PathBuf) -> Result<Self, Box<dyn Error>> {
         let wi = serde_json::from_str::<Self>(fs::read_to_string(path)?.as_str())?;
         let mut names = collections::HashSet::new();
         let mut has_default = false;
         for website in wi.websites.clone().into_iter() {
+            website.validate()?;
             if website.name == wi.default {
                 has_default = true
             }
@@ -33,10 +93,12 @@ impl WebSiteInfo {
                 return Err(format!("Duplicate names: {}", website.name).into());
             }
         }
-        if has_default {
-            Ok(wi)
-        } else {
-            Err(format!("default: {} does not exist", wi.default).into())
+        if !has_default {
+            return Err(format!("default: {} does not exist", wi.default).into());
+        }
+        if wi.slider == Some(0) {
+            return Err("slider: must be greater than 0".into());
         }
+        Ok(wi)
     }
 }
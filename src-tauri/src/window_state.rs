@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+use tauri::{PhysicalPosition, PhysicalSize, Position, Size, Window};
+
+bitflags! {
+    pub struct StateFlags: u32 {
+        const POSITION = 1 << 0;
+        const SIZE = 1 << 1;
+    }
+}
+
+impl Default for StateFlags {
+    fn default() -> Self {
+        StateFlags::POSITION | StateFlags::SIZE
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PersistedState {
+    pub windows: HashMap<String, WindowState>,
+    pub current_id: usize,
+    pub visible: bool,
+}
+
+impl PersistedState {
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn update_window(&mut self, label: &str, window: &Window) {
+        let position = window.outer_position().unwrap_or_default();
+        let size = window.outer_size().unwrap_or_default();
+        self.windows.insert(
+            label.to_string(),
+            WindowState {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            },
+        );
+    }
+}
+
+pub fn is_position_on_screen(window: &Window, state: &WindowState) -> bool {
+    if state.width == 0 || state.height == 0 {
+        return false;
+    }
+    match window.available_monitors() {
+        Ok(monitors) => monitors.into_iter().any(|m| {
+            let pos = m.position();
+            let size = m.size();
+            state.x >= pos.x
+                && state.y >= pos.y
+                && state.x < pos.x + size.width as i32
+                && state.y < pos.y + size.height as i32
+        }),
+        Err(_) => false,
+    }
+}
+
+pub fn restore_window(window: &Window, state: &WindowState, flags: StateFlags) {
+    if flags.contains(StateFlags::POSITION) {
+        let _ = window.set_position(Position::Physical(PhysicalPosition {
+            x: state.x,
+            y: state.y,
+        }));
+    }
+    if flags.contains(StateFlags::SIZE) {
+        let _ = window.set_size(Size::Physical(PhysicalSize {
+            width: state.width,
+            height: state.height,
+        }));
+    }
+}